@@ -1,14 +1,17 @@
 //! This module expose handy C compatible functions to reuse in the different
 //! C style bindings that we have (wallet-c, wallet-jni...)
 
+use crate::select::{CoinControl, OUTPOINT_LENGTH};
 use crate::{Conversion, Error, Proposal, Result, VotePlan, Wallet};
 use chain_impl_mockchain::{
+    block::BlockDate,
     certificate::VotePlanId,
     transaction::Input,
     value::Value,
     vote::{Choice, Options as VoteOptions},
 };
 use std::convert::{TryFrom, TryInto};
+use std::os::raw::c_void;
 use thiserror::Error;
 pub use wallet::Settings;
 
@@ -18,6 +21,54 @@ pub type ConversionPtr = *mut Conversion;
 pub type VotePlanPtr = *mut VotePlan;
 pub type ProposalPtr = *mut Proposal;
 pub type ErrorPtr = *mut Error;
+pub type CoinControlPtr = *mut CoinControl;
+pub type TransactionBuilderPtr = *mut crate::TransactionBuilder;
+pub type UnsignedConversionPtr = *mut crate::UnsignedConversion;
+
+/// the byte length of the sign-data hash an input's witness must cover.
+const SIGN_DATA_HASH_LENGTH: usize = 32;
+
+/// a single event callback, or `None` when the caller did not register one.
+pub type WalletCallback = Option<extern "C" fn(ctx: *mut c_void, value: u64, counter: u32)>;
+
+/// the set of callbacks registered on a wallet through `wallet_set_callbacks`.
+///
+/// `ctx` is an opaque pointer passed back verbatim to every callback; the wallet
+/// never dereferences it. Any of the function pointers may be null, in which
+/// case the corresponding event is simply not reported.
+#[derive(Clone, Copy)]
+pub struct WalletCallbacks {
+    pub ctx: *mut c_void,
+    pub on_funds_received: WalletCallback,
+    pub on_funds_spent: WalletCallback,
+    pub on_state_updated: WalletCallback,
+}
+
+/// report a change in the wallet's tracked value to the registered callbacks.
+///
+/// A positive delta fires `on_funds_received`, a negative one `on_funds_spent`;
+/// an unchanged value fires nothing. `lane` is the spending-counter lane whose
+/// counter is reported to the callback — the lane the transaction consumed, or
+/// lane 0 for events (such as `wallet_retrieve_funds`) that do not advance a
+/// specific lane.
+unsafe fn notify_value_change(wallet: &Wallet, before: u64, lane: u8) {
+    let callbacks = match wallet.callbacks() {
+        Some(callbacks) => callbacks,
+        None => return,
+    };
+    let after = *wallet.total_value().as_ref();
+    let counter = wallet.spending_counter(lane);
+
+    if after > before {
+        if let Some(on_funds_received) = callbacks.on_funds_received {
+            on_funds_received(callbacks.ctx, after - before, counter);
+        }
+    } else if before > after {
+        if let Some(on_funds_spent) = callbacks.on_funds_spent {
+            on_funds_spent(callbacks.ctx, before - after, counter);
+        }
+    }
+}
 
 #[derive(Debug, Error)]
 #[error("null pointer")]
@@ -178,8 +229,11 @@ pub unsafe fn wallet_retrieve_funds(
 
     let block0_bytes = std::slice::from_raw_parts(block0, block0_length);
 
+    let before = *wallet.total_value().as_ref();
+
     match wallet.retrieve_funds(block0_bytes) {
         Ok(settings) => {
+            notify_value_change(wallet, before, 0);
             *settings_out = Box::into_raw(Box::new(settings));
             Result::success()
         }
@@ -223,13 +277,181 @@ pub unsafe fn wallet_convert(
         return Error::invalid_input("conversion_out").with(NulPtr).into();
     };
 
+    let before = *wallet.total_value().as_ref();
     let conversion = wallet.convert(settings);
+    notify_value_change(wallet, before, 0);
 
     *conversion_out = Box::into_raw(Box::new(conversion));
 
     Result::success()
 }
 
+/// like `wallet_convert` but driven by a manual coin-control handle so the
+/// caller can force or exclude specific UTxOs and pick the change policy.
+///
+/// Passing a null `coin_control` is equivalent to calling `wallet_convert`.
+///
+/// # Safety
+///
+/// This function dereference raw pointers (wallet, settings, coin_control and
+/// conversion_out). Even though the function checks if the pointers are null.
+/// Mind not to put random values in or you may see unexpected behaviors
+///
+pub unsafe fn wallet_convert_with_coin_control(
+    wallet: WalletPtr,
+    settings: SettingsPtr,
+    coin_control: CoinControlPtr,
+    conversion_out: *mut ConversionPtr,
+) -> Result {
+    let wallet: &mut Wallet = if let Some(wallet) = wallet.as_mut() {
+        wallet
+    } else {
+        return Error::invalid_input("wallet").with(NulPtr).into();
+    };
+    let settings = if let Some(settings) = settings.as_ref() {
+        settings.clone()
+    } else {
+        return Error::invalid_input("settings").with(NulPtr).into();
+    };
+    let conversion_out: &mut ConversionPtr = if let Some(conversion_out) = conversion_out.as_mut() {
+        conversion_out
+    } else {
+        return Error::invalid_input("conversion_out").with(NulPtr).into();
+    };
+
+    let coin_control = coin_control.as_ref().cloned().unwrap_or_default();
+
+    let before = *wallet.total_value().as_ref();
+    let conversion = wallet.convert_with(settings, &coin_control);
+    notify_value_change(wallet, before, 0);
+
+    *conversion_out = Box::into_raw(Box::new(conversion));
+
+    Result::success()
+}
+
+/// create a new, empty coin-control handle.
+///
+/// The handle starts with no forced or excluded outpoints and the default
+/// change policy. Use `wallet_coin_control_include` / `_exclude` to populate it
+/// and `wallet_delete_coin_control` to free it.
+///
+/// # Safety
+///
+/// This function dereference raw pointers (coin_control_out). Even though the
+/// function checks if the pointers are null. Mind not to put random values in or
+/// you may see unexpected behaviors
+///
+pub unsafe fn wallet_coin_control_new(coin_control_out: *mut CoinControlPtr) -> Result {
+    let coin_control_out = if let Some(coin_control_out) = coin_control_out.as_mut() {
+        coin_control_out
+    } else {
+        return Error::invalid_input("coin_control_out").with(NulPtr).into();
+    };
+
+    *coin_control_out = Box::into_raw(Box::new(CoinControl::default()));
+
+    Result::success()
+}
+
+/// force the output identified by `outpoint` to be spent by the next conversion
+/// or vote transaction built with this handle.
+///
+/// `outpoint` must point to `crate::select::OUTPOINT_LENGTH` bytes: the 32 byte
+/// fragment id followed by the 1 byte output index.
+///
+/// # Safety
+///
+/// This function dereference raw pointers (coin_control and outpoint). Even
+/// though the function checks if the pointers are null. Mind not to put random
+/// values in or you may see unexpected behaviors
+///
+pub unsafe fn wallet_coin_control_include(
+    coin_control: CoinControlPtr,
+    outpoint: *const u8,
+) -> Result {
+    let coin_control = if let Some(coin_control) = coin_control.as_mut() {
+        coin_control
+    } else {
+        return Error::invalid_input("coin_control").with(NulPtr).into();
+    };
+    if outpoint.is_null() {
+        return Error::invalid_input("outpoint").with(NulPtr).into();
+    }
+
+    let mut key = [0u8; OUTPOINT_LENGTH];
+    key.copy_from_slice(std::slice::from_raw_parts(outpoint, OUTPOINT_LENGTH));
+    coin_control.include(key);
+
+    Result::success()
+}
+
+/// forbid the output identified by `outpoint` from being spent by transactions
+/// built with this handle. See `wallet_coin_control_include` for the expected
+/// `outpoint` layout.
+///
+/// # Safety
+///
+/// This function dereference raw pointers (coin_control and outpoint). Even
+/// though the function checks if the pointers are null. Mind not to put random
+/// values in or you may see unexpected behaviors
+///
+pub unsafe fn wallet_coin_control_exclude(
+    coin_control: CoinControlPtr,
+    outpoint: *const u8,
+) -> Result {
+    let coin_control = if let Some(coin_control) = coin_control.as_mut() {
+        coin_control
+    } else {
+        return Error::invalid_input("coin_control").with(NulPtr).into();
+    };
+    if outpoint.is_null() {
+        return Error::invalid_input("outpoint").with(NulPtr).into();
+    }
+
+    let mut key = [0u8; OUTPOINT_LENGTH];
+    key.copy_from_slice(std::slice::from_raw_parts(outpoint, OUTPOINT_LENGTH));
+    coin_control.exclude(key);
+
+    Result::success()
+}
+
+/// set the change policy of the handle: when `avoid_change` is true the
+/// branch-and-bound search accepts only selections that hit the target exactly.
+/// When no exact fit is found, the largest-first fallback can still produce a
+/// change output, and so can forced outpoints that exceed the target. The
+/// default policy allows change.
+///
+/// # Safety
+///
+/// This function dereference raw pointers (coin_control). Even though the
+/// function checks if the pointers are null. Mind not to put random values in or
+/// you may see unexpected behaviors
+///
+pub unsafe fn wallet_coin_control_set_avoid_change(
+    coin_control: CoinControlPtr,
+    avoid_change: bool,
+) -> Result {
+    let coin_control = if let Some(coin_control) = coin_control.as_mut() {
+        coin_control
+    } else {
+        return Error::invalid_input("coin_control").with(NulPtr).into();
+    };
+
+    coin_control.avoid_change(avoid_change);
+
+    Result::success()
+}
+
+/// delete the pointer and free the allocated memory
+pub fn wallet_delete_coin_control(coin_control: CoinControlPtr) {
+    if !coin_control.is_null() {
+        let boxed = unsafe { Box::from_raw(coin_control) };
+
+        std::mem::drop(boxed);
+    }
+}
+
 /// get the number of transactions built to convert the retrieved wallet
 ///
 /// # Safety
@@ -370,7 +592,13 @@ pub unsafe fn wallet_total_value(wallet: WalletPtr, total_out: *mut u64) -> Resu
 ///
 /// this is the value retrieved from any jormungandr endpoint that allows to query
 /// for the account state. It gives the value associated to the account as well as
-/// the counter.
+/// the spending counters.
+///
+/// The account uses several independent spending counters (one per lane, see
+/// `wallet_vote_cast`) so that more than one transaction can be in flight at a
+/// time. `counters` must point to exactly `wallet::account::SPENDING_COUNTER_LANES`
+/// `u32` values, in lane order, and `counters_length` must match that count;
+/// passing a different length is rejected.
 ///
 /// It is important to be sure to have an updated wallet state before doing any
 /// transactions otherwise future transactions may fail to be accepted by any
@@ -379,16 +607,86 @@ pub unsafe fn wallet_total_value(wallet: WalletPtr, total_out: *mut u64) -> Resu
 /// # Errors
 ///
 /// * this function may fail if the wallet pointer is null;
+/// * the `counters` pointer is null or `counters_length` does not match the
+///   number of lanes;
 ///
-pub fn wallet_set_state(wallet: WalletPtr, value: u64, counter: u32) -> Result {
-    let wallet = if let Some(wallet) = unsafe { wallet.as_mut() } {
+/// # Safety
+///
+/// This function dereference raw pointers (wallet and counters). Even though
+/// the function checks if the pointers are null. Mind not to put random values
+/// in or you may see unexpected behaviors
+///
+pub unsafe fn wallet_set_state(
+    wallet: WalletPtr,
+    value: u64,
+    counters: *const u32,
+    counters_length: usize,
+) -> Result {
+    let wallet = if let Some(wallet) = wallet.as_mut() {
         wallet
     } else {
         return Error::invalid_input("wallet").with(NulPtr).into();
     };
+    if counters.is_null() {
+        return Error::invalid_input("counters").with(NulPtr).into();
+    }
+    if counters_length != wallet::account::SPENDING_COUNTER_LANES {
+        return Error::invalid_input("counters_length").into();
+    }
     let value = Value(value);
+    let counters = std::slice::from_raw_parts(counters, counters_length);
 
-    wallet.set_state(value, counter);
+    wallet.set_state(value, counters);
+
+    if let Some(callbacks) = wallet.callbacks() {
+        if let Some(on_state_updated) = callbacks.on_state_updated {
+            on_state_updated(callbacks.ctx, value.0, wallet.spending_counter(0));
+        }
+    }
+
+    Result::success()
+}
+
+/// register the event callbacks fired when the wallet's observable state changes.
+///
+/// This lets consumers of the C bindings react to balance and state changes
+/// instead of polling `wallet_total_value`:
+///
+/// * `on_funds_received` / `on_funds_spent` fire from `wallet_retrieve_funds` and
+///   after a successful vote or conversion whenever the tracked value moves, with
+///   `value` set to the absolute amount of the change;
+/// * `on_state_updated` fires from `wallet_set_state`.
+///
+/// Callbacks run synchronously on the calling thread. `ctx` is passed back to
+/// each callback untouched, and any callback pointer may be null so callers can
+/// register only the events they care about. Calling this again replaces the
+/// previously registered callbacks.
+///
+/// # Safety
+///
+/// This function dereference raw pointers (wallet). Even though the function
+/// checks if the pointers are null. Mind not to put random values in or you may
+/// see unexpected behaviors
+///
+pub unsafe fn wallet_set_callbacks(
+    wallet: WalletPtr,
+    ctx: *mut c_void,
+    on_funds_received: WalletCallback,
+    on_funds_spent: WalletCallback,
+    on_state_updated: WalletCallback,
+) -> Result {
+    let wallet = if let Some(wallet) = wallet.as_mut() {
+        wallet
+    } else {
+        return Error::invalid_input("wallet").with(NulPtr).into();
+    };
+
+    wallet.set_callbacks(WalletCallbacks {
+        ctx,
+        on_funds_received,
+        on_funds_spent,
+        on_state_updated,
+    });
 
     Result::success()
 }
@@ -471,10 +769,26 @@ pub unsafe fn wallet_vote_proposal(
 
 /// build the vote cast transaction
 ///
+/// `valid_until_epoch` and `valid_until_slot` give the block date after which
+/// the transaction is no longer valid; it is encoded as the transaction's expiry
+/// and checked against the current slot in `settings` so an already-expired date
+/// is rejected before signing.
+///
+/// `lane` selects one of the account's independent spending counters (see
+/// `wallet_set_state`); only that lane's counter is consumed and advanced, which
+/// allows several transactions to be in flight in parallel without one blocking
+/// the others.
+///
+/// On success the serialized transaction is returned through `transaction_out` /
+/// `len_out` and its fragment id is written to `id_out`, a caller-allocated array
+/// of `crate::vote::FRAGMENT_ID_LENGTH` bytes, so the transaction can be tracked
+/// until it is confirmed.
+///
 /// # Errors
 ///
-/// This function may fail upon receiving a null pointer or a `choice` value
-/// that does not fall within the range specified in `proposal`.
+/// This function may fail upon receiving a null pointer, a `choice` value that
+/// does not fall within the range specified in `proposal`, a `lane` that is out
+/// of range, or a `valid_until` date that is already expired.
 ///
 /// # Safety
 ///
@@ -487,8 +801,12 @@ pub unsafe fn wallet_vote_cast(
     vote_plan: VotePlanPtr,
     proposal: ProposalPtr,
     choice: u8,
+    valid_until_epoch: u32,
+    valid_until_slot: u32,
+    lane: u8,
     transaction_out: *mut *const u8,
     len_out: *mut usize,
+    id_out: *mut u8,
 ) -> Result {
     let wallet = if let Some(wallet) = wallet.as_mut() {
         wallet
@@ -520,10 +838,435 @@ pub unsafe fn wallet_vote_cast(
     if len_out.is_null() {
         return Error::invalid_input("len_out").with(NulPtr).into();
     }
+    if id_out.is_null() {
+        return Error::invalid_input("id_out").with(NulPtr).into();
+    }
 
     let choice = Choice::new(choice);
+    let valid_until = BlockDate {
+        epoch: valid_until_epoch,
+        slot_id: valid_until_slot,
+    };
 
-    let transaction = match wallet.vote(settings, vote_plan, proposal, choice) {
+    let before = *wallet.total_value().as_ref();
+    let transaction = match wallet.vote(settings, vote_plan, proposal, choice, valid_until, lane) {
+        Ok(transaction) => transaction,
+        Err(err) => return err.into(),
+    };
+    notify_value_change(wallet, before, lane);
+
+    let id_out = std::slice::from_raw_parts_mut(id_out, crate::vote::FRAGMENT_ID_LENGTH);
+    id_out.copy_from_slice(transaction.id().as_ref());
+
+    *transaction_out = transaction.as_ref().as_ptr();
+    *len_out = transaction.as_ref().len();
+
+    Result::success()
+}
+
+/// get the number of outputs currently tracked by the wallet's UTxO set.
+///
+/// This is the upper bound (exclusive) for the `index` accepted by
+/// `wallet_utxo_get`.
+///
+/// # Safety
+///
+/// This function dereference raw pointers. Even though the function checks if
+/// the pointers are null. Mind not to put random values in or you may see
+/// unexpected behaviors
+///
+pub unsafe fn wallet_utxos_size(wallet: WalletPtr) -> usize {
+    wallet.as_ref().map(|w| w.utxos().len()).unwrap_or_default()
+}
+
+/// inspect the `index`-nth output of the wallet's UTxO set.
+///
+/// `outpoint_out`, when not null, must point to `crate::select::OUTPOINT_LENGTH`
+/// bytes and receives the output's outpoint (fragment id + output index).
+/// `value_out` receives the output value and `spendable_out` whether the output
+/// is currently spendable (above the dust threshold). Per-transaction exclusions
+/// live on a `CoinControl` handle rather than on the UTxO set, so they are not
+/// reflected here. Any of the out pointers may be null to skip that field.
+///
+/// # Safety
+///
+/// This function dereference raw pointers. Even though the function checks if
+/// the pointers are null. Mind not to put random values in or you may see
+/// unexpected behaviors
+///
+pub unsafe fn wallet_utxo_get(
+    wallet: WalletPtr,
+    index: usize,
+    outpoint_out: *mut u8,
+    value_out: *mut u64,
+    spendable_out: *mut bool,
+) -> Result {
+    let wallet = if let Some(wallet) = wallet.as_ref() {
+        wallet
+    } else {
+        return Error::invalid_input("wallet").with(NulPtr).into();
+    };
+
+    let utxo = if let Some(utxo) = wallet.utxos().get(index) {
+        utxo
+    } else {
+        return Error::invalid_input("index").with(OutOfBound).into();
+    };
+
+    if !outpoint_out.is_null() {
+        let outpoint_out = std::slice::from_raw_parts_mut(outpoint_out, OUTPOINT_LENGTH);
+        outpoint_out.copy_from_slice(&utxo.outpoint());
+    }
+    if let Some(value_out) = value_out.as_mut() {
+        *value_out = utxo.value().0;
+    }
+    if let Some(spendable_out) = spendable_out.as_mut() {
+        *spendable_out = utxo.is_spendable();
+    }
+
+    Result::success()
+}
+
+/// look up a single output by its outpoint.
+///
+/// `outpoint` must point to `crate::select::OUTPOINT_LENGTH` bytes. When the
+/// output is part of the wallet's UTxO set `found_out` is set to `true` and
+/// `value_out` to its value; otherwise `found_out` is set to `false` and
+/// `value_out` is left untouched.
+///
+/// # Safety
+///
+/// This function dereference raw pointers. Even though the function checks if
+/// the pointers are null. Mind not to put random values in or you may see
+/// unexpected behaviors
+///
+pub unsafe fn wallet_get_utxo(
+    wallet: WalletPtr,
+    outpoint: *const u8,
+    value_out: *mut u64,
+    found_out: *mut bool,
+) -> Result {
+    let wallet = if let Some(wallet) = wallet.as_ref() {
+        wallet
+    } else {
+        return Error::invalid_input("wallet").with(NulPtr).into();
+    };
+    if outpoint.is_null() {
+        return Error::invalid_input("outpoint").with(NulPtr).into();
+    }
+
+    let mut key = [0u8; OUTPOINT_LENGTH];
+    key.copy_from_slice(std::slice::from_raw_parts(outpoint, OUTPOINT_LENGTH));
+
+    match wallet.get_utxo(&key) {
+        Some(utxo) => {
+            if let Some(value_out) = value_out.as_mut() {
+                *value_out = utxo.value().0;
+            }
+            if let Some(found_out) = found_out.as_mut() {
+                *found_out = true;
+            }
+        }
+        None => {
+            if let Some(found_out) = found_out.as_mut() {
+                *found_out = false;
+            }
+        }
+    }
+
+    Result::success()
+}
+
+/// build an unsigned vote cast transaction for an external/hardware signer.
+///
+/// This performs everything `wallet_vote_cast` does except producing the input
+/// witnesses: the transaction body is assembled and the chosen lane's spending
+/// counter is reserved, but signing is left to the caller. The resulting
+/// `builder_out` exposes the body bytes (`wallet_transaction_builder_body`) and
+/// one sign-data hash per input (`wallet_transaction_builder_witness_count` /
+/// `wallet_transaction_builder_sign_data`). Once every witness has been produced
+/// offline, attach them with `wallet_transaction_add_witness` and assemble the
+/// final transaction with `wallet_transaction_finalize`.
+///
+/// # Safety
+///
+/// This function dereference raw pointers. Even though the function checks if
+/// the pointers are null. Mind not to put random values in or you may see
+/// unexpected behaviors.
+pub unsafe fn wallet_vote_cast_build_unsigned(
+    wallet: WalletPtr,
+    settings: SettingsPtr,
+    vote_plan: VotePlanPtr,
+    proposal: ProposalPtr,
+    choice: u8,
+    valid_until_epoch: u32,
+    valid_until_slot: u32,
+    lane: u8,
+    builder_out: *mut TransactionBuilderPtr,
+) -> Result {
+    let wallet = if let Some(wallet) = wallet.as_mut() {
+        wallet
+    } else {
+        return Error::invalid_input("wallet").with(NulPtr).into();
+    };
+    let settings = if let Some(settings) = settings.as_ref() {
+        settings.clone()
+    } else {
+        return Error::invalid_input("settings").with(NulPtr).into();
+    };
+    let vote_plan = if let Some(vote_plan) = vote_plan.as_ref() {
+        vote_plan
+    } else {
+        return Error::invalid_input("vote_plan").with(NulPtr).into();
+    };
+    let proposal = if let Some(proposal) = proposal.as_ref() {
+        proposal
+    } else {
+        return Error::invalid_input("proposal").with(NulPtr).into();
+    };
+    let builder_out = if let Some(builder_out) = builder_out.as_mut() {
+        builder_out
+    } else {
+        return Error::invalid_input("builder_out").with(NulPtr).into();
+    };
+
+    let choice = Choice::new(choice);
+    let valid_until = BlockDate {
+        epoch: valid_until_epoch,
+        slot_id: valid_until_slot,
+    };
+
+    let builder = match wallet.vote_build_unsigned(settings, vote_plan, proposal, choice, valid_until, lane)
+    {
+        Ok(builder) => builder,
+        Err(err) => return err.into(),
+    };
+
+    *builder_out = Box::into_raw(Box::new(builder));
+
+    Result::success()
+}
+
+/// build the set of unsigned conversion transactions for an external signer.
+///
+/// This is the counterpart of `wallet_convert` for air-gapped custody: it
+/// produces one `TransactionBuilder` per conversion transaction, enumerated with
+/// `wallet_unsigned_conversion_size` / `wallet_unsigned_conversion_get`.
+///
+/// # Safety
+///
+/// This function dereference raw pointers. Even though the function checks if
+/// the pointers are null. Mind not to put random values in or you may see
+/// unexpected behaviors.
+pub unsafe fn wallet_convert_build_unsigned(
+    wallet: WalletPtr,
+    settings: SettingsPtr,
+    unsigned_out: *mut UnsignedConversionPtr,
+) -> Result {
+    let wallet = if let Some(wallet) = wallet.as_mut() {
+        wallet
+    } else {
+        return Error::invalid_input("wallet").with(NulPtr).into();
+    };
+    let settings = if let Some(settings) = settings.as_ref() {
+        settings.clone()
+    } else {
+        return Error::invalid_input("settings").with(NulPtr).into();
+    };
+    let unsigned_out = if let Some(unsigned_out) = unsigned_out.as_mut() {
+        unsigned_out
+    } else {
+        return Error::invalid_input("unsigned_out").with(NulPtr).into();
+    };
+
+    let unsigned = wallet.convert_build_unsigned(settings);
+
+    *unsigned_out = Box::into_raw(Box::new(unsigned));
+
+    Result::success()
+}
+
+/// get the number of unsigned transactions in an unsigned conversion
+///
+/// # Safety
+///
+/// This function dereference raw pointers. Even though the function checks if
+/// the pointers are null. Mind not to put random values in or you may see
+/// unexpected behaviors.
+pub unsafe fn wallet_unsigned_conversion_size(unsigned: UnsignedConversionPtr) -> usize {
+    unsigned.as_ref().map(|u| u.len()).unwrap_or_default()
+}
+
+/// borrow the index-nth builder of an unsigned conversion; the returned pointer
+/// is owned by the conversion and must not outlive it or be deleted separately.
+///
+/// # Safety
+///
+/// This function dereference raw pointers. Even though the function checks if
+/// the pointers are null. Mind not to put random values in or you may see
+/// unexpected behaviors.
+pub unsafe fn wallet_unsigned_conversion_get(
+    unsigned: UnsignedConversionPtr,
+    index: usize,
+    builder_out: *mut TransactionBuilderPtr,
+) -> Result {
+    let unsigned = if let Some(unsigned) = unsigned.as_mut() {
+        unsigned
+    } else {
+        return Error::invalid_input("unsigned").with(NulPtr).into();
+    };
+    let builder_out = if let Some(builder_out) = builder_out.as_mut() {
+        builder_out
+    } else {
+        return Error::invalid_input("builder_out").with(NulPtr).into();
+    };
+
+    if let Some(builder) = unsigned.get_mut(index) {
+        *builder_out = builder as TransactionBuilderPtr;
+        Result::success()
+    } else {
+        Error::wallet_conversion().with(OutOfBound).into()
+    }
+}
+
+/// get the unsigned transaction body bytes held by a builder.
+///
+/// the memory returned is owned by the builder and should not be kept for
+/// longer than a potential call to `wallet_delete_transaction_builder`.
+///
+/// # Safety
+///
+/// This function dereference raw pointers. Even though the function checks if
+/// the pointers are null. Mind not to put random values in or you may see
+/// unexpected behaviors.
+pub unsafe fn wallet_transaction_builder_body(
+    builder: TransactionBuilderPtr,
+    body_out: *mut *const u8,
+    len_out: *mut usize,
+) -> Result {
+    let builder = if let Some(builder) = builder.as_ref() {
+        builder
+    } else {
+        return Error::invalid_input("builder").with(NulPtr).into();
+    };
+    if body_out.is_null() {
+        return Error::invalid_input("body_out").with(NulPtr).into();
+    }
+    if len_out.is_null() {
+        return Error::invalid_input("len_out").with(NulPtr).into();
+    }
+
+    let body = builder.body();
+    *body_out = body.as_ptr();
+    *len_out = body.len();
+
+    Result::success()
+}
+
+/// get the number of inputs (and therefore witnesses) the builder expects.
+///
+/// # Safety
+///
+/// This function dereference raw pointers. Even though the function checks if
+/// the pointers are null. Mind not to put random values in or you may see
+/// unexpected behaviors.
+pub unsafe fn wallet_transaction_builder_witness_count(builder: TransactionBuilderPtr) -> usize {
+    builder.as_ref().map(|b| b.inputs_len()).unwrap_or_default()
+}
+
+/// get the sign-data hash the `index`-nth input's witness must cover.
+///
+/// `sign_data_out` must point to `SIGN_DATA_HASH_LENGTH` (32) bytes of
+/// caller-allocated memory.
+///
+/// # Safety
+///
+/// This function dereference raw pointers. Even though the function checks if
+/// the pointers are null. Mind not to put random values in or you may see
+/// unexpected behaviors.
+pub unsafe fn wallet_transaction_builder_sign_data(
+    builder: TransactionBuilderPtr,
+    index: usize,
+    sign_data_out: *mut u8,
+) -> Result {
+    let builder = if let Some(builder) = builder.as_ref() {
+        builder
+    } else {
+        return Error::invalid_input("builder").with(NulPtr).into();
+    };
+    if sign_data_out.is_null() {
+        return Error::invalid_input("sign_data_out").with(NulPtr).into();
+    }
+
+    match builder.sign_data(index) {
+        Some(hash) => {
+            let sign_data_out = std::slice::from_raw_parts_mut(sign_data_out, SIGN_DATA_HASH_LENGTH);
+            sign_data_out.copy_from_slice(hash.as_ref());
+            Result::success()
+        }
+        None => Error::invalid_input("index").with(OutOfBound).into(),
+    }
+}
+
+/// attach an externally produced witness to the `input_index`-nth input.
+///
+/// # Safety
+///
+/// This function dereference raw pointers. Even though the function checks if
+/// the pointers are null. Mind not to put random values in or you may see
+/// unexpected behaviors.
+pub unsafe fn wallet_transaction_add_witness(
+    builder: TransactionBuilderPtr,
+    input_index: usize,
+    witness: *const u8,
+    witness_length: usize,
+) -> Result {
+    let builder = if let Some(builder) = builder.as_mut() {
+        builder
+    } else {
+        return Error::invalid_input("builder").with(NulPtr).into();
+    };
+    if witness.is_null() {
+        return Error::invalid_input("witness").with(NulPtr).into();
+    }
+
+    let witness = std::slice::from_raw_parts(witness, witness_length);
+
+    match builder.add_witness(input_index, witness) {
+        Ok(()) => Result::success(),
+        Err(err) => err.into(),
+    }
+}
+
+/// assemble the fully signed transaction once every witness has been attached.
+///
+/// On success the serialized transaction is returned through `transaction_out` /
+/// `len_out`; the memory is owned by the builder and freed by
+/// `wallet_delete_transaction_builder`.
+///
+/// # Safety
+///
+/// This function dereference raw pointers. Even though the function checks if
+/// the pointers are null. Mind not to put random values in or you may see
+/// unexpected behaviors.
+pub unsafe fn wallet_transaction_finalize(
+    builder: TransactionBuilderPtr,
+    transaction_out: *mut *const u8,
+    len_out: *mut usize,
+) -> Result {
+    let builder = if let Some(builder) = builder.as_mut() {
+        builder
+    } else {
+        return Error::invalid_input("builder").with(NulPtr).into();
+    };
+    if transaction_out.is_null() {
+        return Error::invalid_input("transaction_out").with(NulPtr).into();
+    }
+    if len_out.is_null() {
+        return Error::invalid_input("len_out").with(NulPtr).into();
+    }
+
+    let transaction = match builder.finalize() {
         Ok(transaction) => transaction,
         Err(err) => return err.into(),
     };
@@ -534,6 +1277,24 @@ pub unsafe fn wallet_vote_cast(
     Result::success()
 }
 
+/// delete the pointer and free the allocated memory
+pub fn wallet_delete_transaction_builder(builder: TransactionBuilderPtr) {
+    if !builder.is_null() {
+        let boxed = unsafe { Box::from_raw(builder) };
+
+        std::mem::drop(boxed);
+    }
+}
+
+/// delete the pointer and free the allocated memory
+pub fn wallet_delete_unsigned_conversion(unsigned: UnsignedConversionPtr) {
+    if !unsigned.is_null() {
+        let boxed = unsafe { Box::from_raw(unsigned) };
+
+        std::mem::drop(boxed);
+    }
+}
+
 /// delete the pointer and free the allocated memory
 pub fn wallet_delete_error(error: ErrorPtr) {
     if !error.is_null() {